@@ -0,0 +1,73 @@
+//! The format-agnostic event model that decoders produce and encoders consume.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// The kind of thing that happened in a conversation transcript.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum EventKind {
+    /// Someone said something.
+    Message { name: String, text: String },
+    /// An IRC-style `/me` action, e.g. `* Alice waves`.
+    Action { name: String, text: String },
+    /// Someone joined the conversation.
+    Join { name: String },
+    /// Someone left the conversation.
+    Part { name: String },
+    /// A line that didn't match any known shape, kept verbatim.
+    Other(String),
+}
+
+/// A single parsed line from a transcript, decoupled from whichever format
+/// it was decoded from and whichever format it will be rendered as.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Event {
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+/// Coalesce consecutive `Message` events from the same author into one,
+/// joining their text with a space. Actions, joins, parts, and other events
+/// are left as standalone rows and never merged into or out of a run of
+/// messages. This is the one place merge logic lives, regardless of which
+/// decoder produced the events or which encoder will render them.
+pub fn merge_events(events: Vec<Event>) -> Vec<Event> {
+    let mut merged: Vec<Event> = Vec::new();
+
+    for event in events {
+        if let EventKind::Message { name, text } = &event.kind {
+            if let Some(Event {
+                kind: EventKind::Message {
+                    name: prev_name,
+                    text: prev_text,
+                },
+                ..
+            }) = merged.last_mut()
+            {
+                if prev_name == name {
+                    let last_char = prev_text.trim().chars().last();
+                    prev_text.push(' ');
+                    if last_char == Some('.') {
+                        prev_text.push_str(text);
+                    } else {
+                        prev_text.push_str(&to_lowercase_first(text));
+                    }
+                    continue;
+                }
+            }
+        }
+        merged.push(event);
+    }
+
+    merged
+}
+
+fn to_lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_lowercase().to_string() + chars.as_str(),
+    }
+}
@@ -0,0 +1,14 @@
+use std::io::Write;
+
+use crate::event::Event;
+
+use super::Encode;
+
+/// Renders events as a pretty-printed JSON array.
+pub struct JsonEncoder;
+
+impl Encode for JsonEncoder {
+    fn encode(&self, events: &[Event], writer: impl Write) {
+        serde_json::to_writer_pretty(writer, events).expect("failed to write JSON");
+    }
+}
@@ -0,0 +1,43 @@
+use std::io::Write;
+
+use crate::event::{Event, EventKind};
+
+use super::Encode;
+
+/// Renders events as a Markdown table.
+pub struct MarkdownEncoder;
+
+impl Encode for MarkdownEncoder {
+    fn encode(&self, events: &[Event], mut writer: impl Write) {
+        writeln!(writer, "| Start Time | Name | Message |").unwrap();
+        writeln!(writer, "|------------|------|--------|").unwrap();
+
+        for event in events {
+            let time = event
+                .timestamp
+                .map(|ts| ts.format("%Y-%m-%dT%H:%M:%S").to_string())
+                .unwrap_or_default();
+            let (name, message): (&str, String) = match &event.kind {
+                EventKind::Message { name, text } => (name, text.clone()),
+                EventKind::Action { name, text } => (name, format!("_{} {}_", name, text)),
+                EventKind::Join { name } => (name, "_joined_".to_string()),
+                EventKind::Part { name } => (name, "_left_".to_string()),
+                EventKind::Other(line) => ("", line.clone()),
+            };
+            writeln!(
+                writer,
+                "| {} | {} | {} |",
+                escape_md(&time),
+                escape_md(name),
+                escape_md(&message)
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// Escape a table cell so it can't break out of its row: `|` would otherwise
+/// misalign the table and a raw newline would split the row across lines.
+pub(crate) fn escape_md(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
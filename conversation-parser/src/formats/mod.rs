@@ -0,0 +1,65 @@
+//! Input/output format plugins.
+//!
+//! A [`Decode`] turns a reader into a stream of [`Event`]s and an [`Encode`]
+//! turns a slice of `Event`s into bytes. `main` picks one of each based on
+//! the `--from`/`--to` flags, so converting between any supported input and
+//! output format is just a matter of pairing the right decoder with the
+//! right encoder — the merge logic in between never changes.
+
+pub mod json;
+pub mod markdown;
+pub mod msgpack;
+pub mod quoted;
+pub mod text;
+
+use std::io::{BufRead, Write};
+
+use crate::event::Event;
+
+/// Decodes a transcript in some input format into a stream of events.
+pub trait Decode {
+    fn decode(&self, reader: impl BufRead) -> impl Iterator<Item = Event>;
+}
+
+/// Encodes a list of events into some output format.
+pub trait Encode {
+    fn encode(&self, events: &[Event], writer: impl Write);
+}
+
+/// Supported `--from` decoder formats.
+#[derive(Clone, Copy, Debug)]
+pub enum InputFormat {
+    /// `TIMESTAMP - Name: message` plain text.
+    Text,
+    /// Quoted `speaker`/`text` JSON-ish format.
+    Quoted,
+}
+
+impl InputFormat {
+    pub fn parse(flag: &str) -> Option<Self> {
+        match flag {
+            "text" => Some(Self::Text),
+            "quoted" => Some(Self::Quoted),
+            _ => None,
+        }
+    }
+}
+
+/// Supported `--to` encoder formats.
+#[derive(Clone, Copy, Debug)]
+pub enum OutputFormat {
+    Markdown,
+    Json,
+    MsgPack,
+}
+
+impl OutputFormat {
+    pub fn parse(flag: &str) -> Option<Self> {
+        match flag {
+            "markdown" | "md" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            "msgpack" => Some(Self::MsgPack),
+            _ => None,
+        }
+    }
+}
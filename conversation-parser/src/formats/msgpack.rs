@@ -0,0 +1,17 @@
+use std::io::Write;
+
+use crate::event::Event;
+
+use super::Encode;
+
+/// Renders events as MessagePack-encoded bytes.
+pub struct MsgPackEncoder;
+
+impl Encode for MsgPackEncoder {
+    fn encode(&self, events: &[Event], mut writer: impl Write) {
+        let bytes = rmp_serde::to_vec(events).expect("failed to encode MessagePack");
+        writer
+            .write_all(&bytes)
+            .expect("failed to write MessagePack");
+    }
+}
@@ -0,0 +1,28 @@
+use std::io::BufRead;
+
+use crate::event::{Event, EventKind};
+use crate::json_no_timestamps::parse_entries;
+
+use super::Decode;
+
+/// Decodes the quoted `speaker`/`text` JSON-ish transcript format handled by
+/// [`json_no_timestamps`](crate::json_no_timestamps). This format carries no
+/// timestamps, so every decoded event has `timestamp: None`.
+pub struct QuotedDecoder;
+
+impl Decode for QuotedDecoder {
+    fn decode(&self, mut reader: impl BufRead) -> impl Iterator<Item = Event> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .expect("failed to read input");
+
+        parse_entries(&input).into_iter().map(|entry| Event {
+            timestamp: None,
+            kind: EventKind::Message {
+                name: entry.speaker,
+                text: entry.text,
+            },
+        })
+    }
+}
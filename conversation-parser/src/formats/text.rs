@@ -0,0 +1,263 @@
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use regex::Regex;
+
+use crate::event::{Event, EventKind};
+
+use super::Decode;
+
+// Regex to split a line into its timestamp and the rest, e.g.:
+// 2024-05-01T12:00:00 - Alice: Hello, how are you?
+//
+// The timestamp itself is captured loosely (anything up to the ` - `
+// delimiter) so the actual format validation/parsing lives in one place:
+// `parse_timestamp`, not duplicated here.
+static LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.+?) - (.*)$").unwrap());
+
+// The shapes `rest` (the part of the line after the timestamp) can take,
+// tried in order. `* Name does something` is an action, `Name: message`
+// is a plain message, and `Name has joined`/`has left`/`has quit` are
+// presence notices.
+static ACTION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\* (.+?) (.+)$").unwrap());
+static JOIN_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.+?) has joined$").unwrap());
+static PART_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(.+?) has (?:left|quit)$").unwrap());
+static MESSAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.+?): (.+)$").unwrap());
+
+/// Candidate timestamp formats tried in order when no `--time-format`
+/// override is given. Covers the original ISO-T shape, a space-separated
+/// variant, and RFC3339 with a timezone offset.
+const TIMESTAMP_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",    // 2024-05-01T12:00:00
+    "%Y-%m-%d %H:%M:%S",    // 2024-05-01 12:00:00
+    "%Y-%m-%dT%H:%M:%S%:z", // 2024-05-01T12:00:00+02:00
+];
+
+/// Decodes the `TIMESTAMP - Name: message` plain text transcript format.
+pub struct TextDecoder {
+    /// Overrides the auto-detected timestamp format (see `--time-format`).
+    pub time_format: Option<String>,
+}
+
+impl Decode for TextDecoder {
+    fn decode(&self, reader: impl BufRead) -> impl Iterator<Item = Event> {
+        let time_format = self.time_format.clone();
+        reader
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(move |line| parse_message(line.trim(), time_format.as_deref()))
+    }
+}
+
+fn parse_message(line: &str, time_format: Option<&str>) -> Option<Event> {
+    if line.is_empty() {
+        return None;
+    }
+
+    let Some(caps) = LINE_REGEX.captures(line) else {
+        return Some(Event {
+            timestamp: None,
+            kind: EventKind::Other(line.to_string()),
+        });
+    };
+    let raw_timestamp = &caps[1];
+    let rest = &caps[2];
+
+    let ts = match parse_timestamp(raw_timestamp, time_format) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!(
+                "Keeping line with unparseable timestamp '{}' as Other: {}",
+                raw_timestamp, e
+            );
+            return Some(Event {
+                timestamp: None,
+                kind: EventKind::Other(line.to_string()),
+            });
+        }
+    };
+
+    // `MESSAGE_REGEX` is tried before `JOIN_REGEX`/`PART_REGEX`: a "has
+    // joined"/"has left" notice never contains a `: ` delimiter, so any
+    // `rest` that does (e.g. `Bob: Alice has left`) is an ordinary message
+    // and must not be misclassified as a presence notice.
+    let kind = if let Some(caps) = ACTION_REGEX.captures(rest) {
+        EventKind::Action {
+            name: caps[1].to_string(),
+            text: caps[2].to_string(),
+        }
+    } else if let Some(caps) = MESSAGE_REGEX.captures(rest) {
+        EventKind::Message {
+            name: caps[1].to_string(),
+            text: caps[2].to_string(),
+        }
+    } else if let Some(caps) = JOIN_REGEX.captures(rest) {
+        EventKind::Join {
+            name: caps[1].to_string(),
+        }
+    } else if let Some(caps) = PART_REGEX.captures(rest) {
+        EventKind::Part {
+            name: caps[1].to_string(),
+        }
+    } else {
+        EventKind::Other(rest.to_string())
+    };
+
+    Some(Event {
+        timestamp: Some(ts),
+        kind,
+    })
+}
+
+/// Parse `s` as a timestamp, normalizing to UTC.
+///
+/// If `time_format` is given it's the only format tried; otherwise each of
+/// `TIMESTAMP_FORMATS` is tried in order. For every format we first try
+/// parsing it as an offset-bearing `DateTime<FixedOffset>` (so timezone
+/// offsets are honored and converted to UTC) and fall back to a naive,
+/// already-UTC timestamp when the format carries no offset.
+fn parse_timestamp(s: &str, time_format: Option<&str>) -> Result<DateTime<Utc>, chrono::ParseError> {
+    if let Some(format) = time_format {
+        return parse_with_format(s, format);
+    }
+
+    let mut last_err = None;
+    for format in TIMESTAMP_FORMATS {
+        match parse_with_format(s, format) {
+            Ok(ts) => return Ok(ts),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err.expect("TIMESTAMP_FORMATS is non-empty"))
+}
+
+/// Try `format` as an offset-bearing timestamp first, falling back to a
+/// naive (already-UTC) timestamp when `format` carries no offset.
+fn parse_with_format(s: &str, format: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
+    if let Ok(dt) = DateTime::parse_from_str(s, format) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(s, format).map(|ndt| ndt.and_utc())
+}
+
+#[cfg(test)]
+mod timestamp_tests {
+    use super::*;
+
+    #[test]
+    fn parses_iso_t_timestamp() {
+        let ts = parse_timestamp("2024-05-01T12:00:00", None).unwrap();
+        assert_eq!(ts.to_string(), "2024-05-01 12:00:00 UTC");
+    }
+
+    #[test]
+    fn parses_space_separated_timestamp() {
+        let ts = parse_timestamp("2024-05-01 12:00:00", None).unwrap();
+        assert_eq!(ts.to_string(), "2024-05-01 12:00:00 UTC");
+    }
+
+    #[test]
+    fn normalizes_offset_timestamp_to_utc() {
+        let ts = parse_timestamp("2024-05-01T14:00:00+02:00", None).unwrap();
+        assert_eq!(ts.to_string(), "2024-05-01 12:00:00 UTC");
+    }
+
+    #[test]
+    fn time_format_override_takes_precedence_over_candidates() {
+        let ts = parse_timestamp("01/05/2024 12:00:00", Some("%d/%m/%Y %H:%M:%S")).unwrap();
+        assert_eq!(ts.to_string(), "2024-05-01 12:00:00 UTC");
+    }
+
+    #[test]
+    fn rejects_unrecognized_timestamp() {
+        assert!(parse_timestamp("not a timestamp", None).is_err());
+    }
+}
+
+#[cfg(test)]
+mod classification_tests {
+    use super::*;
+
+    #[test]
+    fn classifies_action() {
+        let event = parse_message("2024-05-01T12:00:00 - * Alice waves", None).unwrap();
+        assert_eq!(
+            event.kind,
+            EventKind::Action {
+                name: "Alice".to_string(),
+                text: "waves".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn classifies_plain_message() {
+        let event = parse_message("2024-05-01T12:00:00 - Alice: Hello there", None).unwrap();
+        assert_eq!(
+            event.kind,
+            EventKind::Message {
+                name: "Alice".to_string(),
+                text: "Hello there".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn classifies_join() {
+        let event = parse_message("2024-05-01T12:00:00 - Alice has joined", None).unwrap();
+        assert_eq!(
+            event.kind,
+            EventKind::Join {
+                name: "Alice".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn classifies_part() {
+        let event = parse_message("2024-05-01T12:00:00 - Alice has left", None).unwrap();
+        assert_eq!(
+            event.kind,
+            EventKind::Part {
+                name: "Alice".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn message_mentioning_join_part_text_is_not_misclassified() {
+        let event =
+            parse_message("2024-05-01T12:00:00 - Bob: Alice has left the building", None)
+                .unwrap();
+        assert_eq!(
+            event.kind,
+            EventKind::Message {
+                name: "Bob".to_string(),
+                text: "Alice has left the building".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn line_whose_prefix_is_not_a_timestamp_is_other() {
+        let event = parse_message("well - I think so", None).unwrap();
+        assert_eq!(
+            event.kind,
+            EventKind::Other("well - I think so".to_string())
+        );
+        assert_eq!(event.timestamp, None);
+    }
+
+    #[test]
+    fn line_with_unparseable_timestamp_falls_back_to_other() {
+        let event = parse_message("not a timestamp - Alice: Hello", None).unwrap();
+        assert_eq!(
+            event.kind,
+            EventKind::Other("not a timestamp - Alice: Hello".to_string())
+        );
+        assert_eq!(event.timestamp, None);
+    }
+}
@@ -1,158 +1,278 @@
-use std::fs;
-use std::io::Read;
+//! A small JSON reader, good enough to extract `speaker`/`text` pairs from
+//! real, minified, or deeply nested JSON transcripts.
+//!
+//! This works like a Lisp reader: [`tokenize`] turns the input into a flat
+//! token stream, and [`Reader`] recursively consumes that stream into a
+//! [`Value`] tree, which [`parse_entries`] then walks.
 
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// A single speaker/text pair extracted from the input.
 #[derive(Debug)]
-struct Entry {
-    speaker: String,
-    text: String,
+pub struct Entry {
+    pub speaker: String,
+    pub text: String,
 }
 
-/// Parse a quoted string starting right after the opening `"`
-/// Returns (parsed_string, remaining_input_after_closing_quote)
-fn parse_quoted(s: &str) -> Option<(String, &str)> {
-    let mut result = String::new();
-    let mut chars = s.char_indices();
-    loop {
-        match chars.next() {
-            None => return None,
-            Some((i, '\\')) => match chars.next() {
-                Some((_, '"')) => result.push('"'),
-                Some((_, 'n')) => result.push('\n'),
-                Some((_, 't')) => result.push('\t'),
-                Some((_, '\\')) => result.push('\\'),
-                Some((_, c)) => {
-                    result.push('\\');
-                    result.push(c);
-                }
-                None => return None,
-            },
-            Some((i, '"')) => {
-                let rest = &s[i + 1..];
-                return Some((result, rest));
-            }
-            Some((_, c)) => result.push(c),
+/// A parsed JSON value.
+///
+/// `speaker`/`text` extraction only ever reads `Value::String`; `Bool` and
+/// `Number` exist so the recursive descent can fully parse (and correctly
+/// skip over) any JSON value, not just strings.
+#[derive(Debug, Clone)]
+enum Value {
+    Null,
+    #[allow(dead_code)]
+    Bool(bool),
+    #[allow(dead_code)]
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+// Emits one token per match: a structural character, a quoted string
+// (escapes handled separately, in `unescape_string`), or a run of anything
+// else (numbers, `true`/`false`/`null`, and bare/unquoted keys).
+static TOKEN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"[{}\[\]:,]|"(?:\\.|[^\\"])*"|[^\s{}\[\]:,"]+"#).unwrap()
+});
+
+fn tokenize(input: &str) -> Vec<String> {
+    TOKEN_REGEX
+        .find_iter(input)
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+/// Consumes a flat token stream and recursively parses it into a [`Value`].
+struct Reader {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Reader {
+    fn new(tokens: Vec<String>) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        if token.is_some() {
+            self.pos += 1;
         }
+        token
     }
-}
 
-fn parse_entries(input: &str) -> Vec<Entry> {
-    let mut entries = Vec::new();
+    fn parse_value(&mut self) -> Option<Value> {
+        match self.peek()? {
+            "{" => self.parse_object(),
+            "[" => self.parse_array(),
+            "true" => {
+                self.next();
+                Some(Value::Bool(true))
+            }
+            "false" => {
+                self.next();
+                Some(Value::Bool(false))
+            }
+            "null" => {
+                self.next();
+                Some(Value::Null)
+            }
+            token if token.starts_with('"') => {
+                Some(Value::String(unescape_string(&self.next().unwrap())))
+            }
+            _ => self.next()?.parse::<f64>().ok().map(Value::Number),
+        }
+    }
 
-    // Split into lines and parse line-by-line
-    // Lines look like:  speaker: "value",  or  text: "value"
-    let mut current_speaker: Option<String> = None;
-
-    for line in input.lines() {
-        let trimmed = line.trim();
-
-        // Check for speaker: "..."
-        let (key, rest) = if let Some(rest) = trimmed.strip_prefix("speaker:") {
-            ("speaker", rest)
-        } else if let Some(rest) = trimmed.strip_prefix("\"speaker\":") {
-            ("speaker", rest)
-        } else if let Some(rest) = trimmed.strip_prefix("text:") {
-            ("text", rest)
-        } else if let Some(rest) = trimmed.strip_prefix("\"text\":") {
-            ("text", rest)
-        } else {
-            continue;
-        };
+    fn parse_object(&mut self) -> Option<Value> {
+        self.next(); // consume '{'
+        let mut fields = Vec::new();
 
-        // Find opening quote for the value
-        let rest = rest.trim();
-        if !rest.starts_with('"') {
-            continue;
+        if self.peek() == Some("}") {
+            self.next();
+            return Some(Value::Object(fields));
         }
-        let rest = &rest[1..]; // skip opening "
 
-        let (value, _) = match parse_quoted(rest) {
-            Some(v) => v,
-            None => continue,
-        };
+        loop {
+            let key_token = self.next()?;
+            let key = if key_token.starts_with('"') {
+                unescape_string(&key_token)
+            } else {
+                key_token
+            };
 
-        match key {
-            "speaker" => {
-                current_speaker = Some(value);
+            if self.next().as_deref() != Some(":") {
+                return None;
             }
-            "text" => {
-                if let Some(speaker) = current_speaker.take() {
-                    entries.push(Entry {
-                        speaker,
-                        text: value,
-                    });
-                }
+            fields.push((key, self.parse_value()?));
+
+            match self.next()?.as_str() {
+                "," => continue,
+                "}" => break,
+                _ => return None,
             }
-            _ => {}
         }
+
+        Some(Value::Object(fields))
     }
 
-    entries
+    fn parse_array(&mut self) -> Option<Value> {
+        self.next(); // consume '['
+        let mut items = Vec::new();
+
+        if self.peek() == Some("]") {
+            self.next();
+            return Some(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.parse_value()?);
+            match self.next()?.as_str() {
+                "," => continue,
+                "]" => break,
+                _ => return None,
+            }
+        }
+
+        Some(Value::Array(items))
+    }
 }
 
-fn make_lowercase_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(c) => c.to_lowercase().collect::<String>() + chars.as_str(),
+/// Unescape a quoted token, including its surrounding quotes: `\"`, `\n`,
+/// `\t`, `\\`, and anything else passed through as-is.
+fn unescape_string(token: &str) -> String {
+    let inner = &token[1..token.len() - 1];
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => result.push('"'),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => {}
+        }
     }
+
+    result
 }
 
-fn make_uppercase_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
+fn as_str(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(s) => Some(s.as_str()),
+        _ => None,
     }
 }
 
-fn merge_entries(entries: Vec<Entry>) -> Vec<Entry> {
-    let mut merged: Vec<Entry> = Vec::new();
-
-    for entry in entries {
-        if let Some(last) = merged.last_mut() {
-            if last.speaker == entry.speaker {
-                let ends_with_period = last.text.trim_end().ends_with('.');
-                let next_text = if ends_with_period {
-                    make_uppercase_first(&entry.text)
-                } else {
-                    make_lowercase_first(&entry.text)
-                };
-                last.text.push(' ');
-                last.text.push_str(&next_text);
-                continue;
+fn collect_entries(value: &Value, entries: &mut Vec<Entry>) {
+    match value {
+        Value::Object(fields) => {
+            let speaker = fields
+                .iter()
+                .find_map(|(k, v)| (k == "speaker").then(|| as_str(v)).flatten());
+            let text = fields
+                .iter()
+                .find_map(|(k, v)| (k == "text").then(|| as_str(v)).flatten());
+
+            if let (Some(speaker), Some(text)) = (speaker, text) {
+                entries.push(Entry {
+                    speaker: speaker.to_string(),
+                    text: text.to_string(),
+                });
+            }
+
+            for (_, v) in fields {
+                collect_entries(v, entries);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_entries(item, entries);
             }
         }
-        merged.push(entry);
+        _ => {}
     }
-
-    merged
 }
 
-fn escape_md(s: &str) -> String {
-    s.replace('|', "\\|").replace('\n', " ")
+/// Parse `input` as JSON and walk the resulting tree to collect every
+/// `speaker`/`text` pair, in document order.
+pub fn parse_entries(input: &str) -> Vec<Entry> {
+    let mut reader = Reader::new(tokenize(input));
+    let Some(value) = reader.parse_value() else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    collect_entries(&value, &mut entries);
+    entries
 }
 
-fn to_markdown_table(entries: &[Entry]) -> String {
-    let mut out = String::new();
-    out.push_str("| Speaker | Text |\n");
-    out.push_str("|---------|------|\n");
-    for e in entries {
-        out.push_str(&format!(
-            "| {} | {} |\n",
-            escape_md(&e.speaker),
-            escape_md(&e.text)
-        ));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_object() {
+        let input = r#"{"speaker": "Alice", "text": "hi", "meta": {"nested": true}}"#;
+        let entries = parse_entries(input);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].speaker, "Alice");
+        assert_eq!(entries[0].text, "hi");
     }
-    out
-}
 
-pub fn json_to_md(path: &str, output: &str) {
-    let input = fs::read_to_string(path).expect("Failed to read input file");
-    println!("Read input file '{}'", path);
-    println!("Read input file '{}'", input);
-    let entries = parse_entries(&input);
-    let merged = merge_entries(entries);
-    let table = to_markdown_table(&merged);
+    #[test]
+    fn parses_array_of_objects_on_one_line() {
+        let input = r#"[{"speaker":"Alice","text":"hi"},{"speaker":"Bob","text":"hey"}]"#;
+        let entries = parse_entries(input);
 
-    print!("{}", table);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].speaker, "Alice");
+        assert_eq!(entries[0].text, "hi");
+        assert_eq!(entries[1].speaker, "Bob");
+        assert_eq!(entries[1].text, "hey");
+    }
+
+    #[test]
+    fn unescapes_quoted_strings() {
+        let input = r#"{"speaker": "Alice", "text": "Line one\nLine two \"quoted\" and a \\ backslash"}"#;
+        let entries = parse_entries(input);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].text,
+            "Line one\nLine two \"quoted\" and a \\ backslash"
+        );
+    }
+
+    #[test]
+    fn ignores_objects_missing_speaker_or_text() {
+        let input = r#"{"speaker": "Alice"}"#;
+        assert!(parse_entries(input).is_empty());
+    }
+
+    #[test]
+    fn malformed_input_yields_no_entries() {
+        assert!(parse_entries("{").is_empty());
+        assert!(parse_entries(r#"{"speaker": }"#).is_empty());
+        assert!(parse_entries("not json at all").is_empty());
+    }
 }
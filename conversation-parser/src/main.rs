@@ -1,162 +1,137 @@
+mod event;
+mod formats;
 mod json_no_timestamps;
-use chrono::{DateTime, NaiveDateTime, Utc};
-use regex::Regex;
+mod sessions;
+mod stats;
+
 use std::env;
 use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::sync::LazyLock;
-
-// Regex to match lines like:
-// **2024-05-01T12:00:00 - Alice:** Hello, how are you?
-// static MSG_REGEX: LazyLock<Regex> =
-//     LazyLock::new(|| Regex::new(r"\*\*(.+?) - (.+?):\*\* (.+)").unwrap());
-
-// Regex to match lines like:
-// 2024-05-01T12:00:00 - Alice: Hello, how are you?
-static MSG_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}) - (.+?): (.+)").unwrap());
-
-#[derive(Clone, Debug)]
-struct Conversation {
-    start_time: DateTime<Utc>,
-    end_time: Option<DateTime<Utc>>,
-    name: String,
-    message: String,
-}
+use std::io::{self, BufReader};
+
+use chrono::Duration;
+
+use event::merge_events;
+use formats::{Decode, Encode, InputFormat, OutputFormat};
 
 fn main() -> io::Result<()> {
     let args: Vec<String> = env::args().collect();
-    let file_path = args.get(1).map(String::as_str).unwrap_or("./output.txt");
-    let flag = args.get(2).map(String::as_str).unwrap_or("");
-    if flag == "--help" {
-        eprintln!("Usage: {} [input_file] [--help]", args[0]);
-        eprintln!("  input_file: Path to the input text file (default: './output.txt')");
-        eprintln!("  --help: Show this help message");
-        return Ok(());
-    } else if flag == "json" {
-        json_no_timestamps::json_to_md(file_path, "output.md");
+
+    if args.iter().any(|a| a == "--help") {
+        print_usage(&args[0]);
         return Ok(());
     }
 
-    let conversations = parse_conversations(file_path)?;
+    let positionals = positional_args(&args);
+    let mode = positionals.iter().copied().find(|p| MODES.contains(p));
+    let file_path = positionals
+        .iter()
+        .copied()
+        .find(|p| !MODES.contains(p))
+        .unwrap_or("./output.txt");
+    let from = flag_value(&args, "--from").unwrap_or("text");
+    let to = flag_value(&args, "--to").unwrap_or("markdown");
+    let time_format = flag_value(&args, "--time-format").map(String::from);
+
+    let Some(input_format) = InputFormat::parse(from) else {
+        eprintln!("Unknown --from format '{}'", from);
+        return Ok(());
+    };
+    let Some(output_format) = OutputFormat::parse(to) else {
+        eprintln!("Unknown --to format '{}'", to);
+        return Ok(());
+    };
 
-    if conversations.is_empty() {
+    let reader = BufReader::new(File::open(file_path)?);
+    let events: Vec<_> = match input_format {
+        InputFormat::Text => formats::text::TextDecoder { time_format }
+            .decode(reader)
+            .collect(),
+        InputFormat::Quoted => formats::quoted::QuotedDecoder.decode(reader).collect(),
+    };
+
+    if events.is_empty() {
         eprintln!("No conversations found in '{}'.", file_path);
         return Ok(());
     }
 
-    println!("{}", to_markdown_table(&conversations));
-    Ok(())
-}
-
-/// Parse the file at `path` into a list of merged conversations.
-fn parse_conversations(path: &str) -> io::Result<Vec<Conversation>> {
-    let mut conversations: Vec<Conversation> = Vec::new();
-    let mut current: Option<Conversation> = None;
+    let merged = merge_events(events);
 
-    for line in read_lines(path)? {
-        let line = line?;
+    if mode == Some("freq") {
+        let top_n = flag_value(&args, "--top-words")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let speaker_stats = stats::compute_stats(&merged);
+        println!("{}", stats::to_markdown_table(&speaker_stats, top_n));
+        return Ok(());
+    }
 
-        let Some((timestamp, name, message)) = parse_message(&line) else {
-            continue;
-        };
-
-        let ts = match parse_timestamp(&timestamp) {
-            Ok(t) => t,
-            Err(e) => {
-                eprintln!("Skipping line with bad timestamp '{}': {}", timestamp, e);
-                continue;
-            }
-        };
-
-        match current.as_mut() {
-            // Same author — merge the message into the running conversation
-            Some(prev) if prev.name == name => {
-                let last_char = prev.message.trim().chars().last();
-                if last_char == Some('.') {
-                    prev.message.push(' ');
-                    prev.message.push_str(&message);
-                } else {
-                    prev.message.push(' ');
-                    prev.message.push_str(&to_lowercase_first(&message));
-                }
-            }
-            // Different author — flush the previous conversation and start a new one
-            Some(prev) => {
-                prev.end_time = Some(ts);
-                conversations.push(current.take().unwrap());
-                current = Some(Conversation {
-                    start_time: ts,
-                    end_time: None,
-                    name,
-                    message,
-                });
-            }
-            // Very first message
-            None => {
-                current = Some(Conversation {
-                    start_time: ts,
-                    end_time: None,
-                    name,
-                    message,
-                });
-            }
-        }
+    if mode == Some("sessions") {
+        let idle_gap = flag_value(&args, "--idle-gap")
+            .and_then(|v| v.parse().ok())
+            .map(Duration::minutes)
+            .unwrap_or_else(|| Duration::minutes(30));
+        let sessions = sessions::split_into_sessions(merged, idle_gap);
+        println!("{}", sessions::to_markdown(&sessions));
+        return Ok(());
     }
 
-    // Don't forget to flush the last conversation
-    if let Some(last) = current {
-        conversations.push(last);
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    match output_format {
+        OutputFormat::Markdown => formats::markdown::MarkdownEncoder.encode(&merged, &mut handle),
+        OutputFormat::Json => formats::json::JsonEncoder.encode(&merged, &mut handle),
+        OutputFormat::MsgPack => formats::msgpack::MsgPackEncoder.encode(&merged, &mut handle),
     }
 
-    Ok(conversations)
+    Ok(())
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+fn flag_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
 }
 
-fn parse_message(input: &str) -> Option<(String, String, String)> {
-    MSG_REGEX.captures(input.trim()).map(|caps| {
-        (
-            caps[1].to_string(),
-            caps[2].to_string(),
-            caps[3].to_string(),
-        )
-    })
-}
+const VALUE_FLAGS: &[&str] = &["--from", "--to", "--time-format", "--top-words", "--idle-gap"];
+const MODES: &[&str] = &["freq", "sessions"];
 
-fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
-    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").map(|ndt| ndt.and_utc())
-}
+/// Positional (non-flag) arguments: the program name, `--help`, and every
+/// recognized `--flag value` pair are skipped, so a mode keyword like `freq`
+/// given with no input file isn't mistaken for one.
+fn positional_args(args: &[String]) -> Vec<&str> {
+    let mut positionals = Vec::new();
+    let mut i = 1; // skip the program name
 
-fn to_lowercase_first(s: &str) -> String {
-    let mut chars = s.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_lowercase().to_string() + chars.as_str(),
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if VALUE_FLAGS.contains(&arg) {
+            i += 2; // skip the flag and its value
+            continue;
+        }
+        if arg == "--help" {
+            i += 1;
+            continue;
+        }
+        positionals.push(arg);
+        i += 1;
     }
-}
-
-fn to_markdown_table(conversations: &[Conversation]) -> String {
-    let mut table = String::new();
 
-    table.push_str("| Start Time | Name | Message |\n");
-    table.push_str("|------------|------|--------|\n");
-
-    for conv in conversations {
-        table.push_str(&format!(
-            "| {} | {} | {} |\n",
-            conv.start_time.format("%Y-%m-%dT%H:%M:%S"),
-            conv.name,
-            conv.message
-        ));
-    }
+    positionals
+}
 
-    table
+fn print_usage(bin: &str) {
+    eprintln!(
+        "Usage: {} [input_file] [freq|sessions] [--from <format>] [--to <format>] [--help]",
+        bin
+    );
+    eprintln!("  input_file: Path to the input transcript (default: './output.txt')");
+    eprintln!("  freq: Emit per-speaker statistics instead of the transcript");
+    eprintln!("  sessions: Split the transcript into sessions on idle gaps");
+    eprintln!("  --from: Input format: text (default), quoted");
+    eprintln!("  --to: Output format: markdown (default), json, msgpack");
+    eprintln!("  --time-format: Override the auto-detected timestamp format (text input only)");
+    eprintln!("  --top-words: Number of top words per speaker in `freq` mode (default: 5)");
+    eprintln!("  --idle-gap: Minutes of silence that start a new session in `sessions` mode (default: 30)");
+    eprintln!("  --help: Show this help message");
 }
@@ -0,0 +1,199 @@
+//! Splits a transcript into sessions on idle gaps, for the `sessions` mode.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::event::Event;
+use crate::formats::markdown::MarkdownEncoder;
+use crate::formats::Encode;
+
+/// A contiguous run of events with no gap between timestamped events larger
+/// than the configured idle threshold. `start`/`stop` are `None` when none
+/// of the run's events carry a timestamp (e.g. a transcript decoded with
+/// `--from quoted`, which never carries timestamps) — the run is still kept
+/// as a single timeless session rather than dropped.
+pub struct Session {
+    pub start: Option<DateTime<Utc>>,
+    pub stop: Option<DateTime<Utc>>,
+    pub events: Vec<Event>,
+}
+
+impl Session {
+    /// Clamped to zero: out-of-order timestamps within a single run (clock
+    /// skew, or mixed timestamp formats landing in the same idle window)
+    /// could otherwise make `stop` precede `start` and yield a negative
+    /// duration.
+    pub fn duration(&self) -> Duration {
+        match (self.start, self.stop) {
+            (Some(start), Some(stop)) => (stop - start).max(Duration::zero()),
+            _ => Duration::zero(),
+        }
+    }
+
+    /// Build a session from a run of events, taking its start/stop from the
+    /// earliest and latest timestamped events rather than the first/last in
+    /// event order, since timestamps within a run aren't guaranteed to be
+    /// monotonic.
+    fn from_events(events: Vec<Event>) -> Self {
+        let start = events.iter().filter_map(|e| e.timestamp).min();
+        let stop = events.iter().filter_map(|e| e.timestamp).max();
+
+        if start.is_none() {
+            eprintln!(
+                "Session of {} event(s) has no timestamps; keeping it as a single timeless session",
+                events.len()
+            );
+        }
+
+        Session { start, stop, events }
+    }
+}
+
+/// Group `events` into sessions, starting a new one whenever the gap
+/// between one timestamped event and the next exceeds `idle_gap`. Events
+/// with no timestamp stay in whichever session is currently open.
+pub fn split_into_sessions(events: Vec<Event>, idle_gap: Duration) -> Vec<Session> {
+    let mut runs: Vec<Vec<Event>> = Vec::new();
+    let mut last_timestamp: Option<DateTime<Utc>> = None;
+
+    for event in events {
+        let gap_exceeded = matches!(
+            (last_timestamp, event.timestamp),
+            (Some(last), Some(ts)) if ts - last > idle_gap
+        );
+
+        if gap_exceeded || runs.is_empty() {
+            runs.push(Vec::new());
+        }
+
+        if let Some(ts) = event.timestamp {
+            last_timestamp = Some(ts);
+        }
+        runs.last_mut().unwrap().push(event);
+    }
+
+    runs.into_iter().map(Session::from_events).collect()
+}
+
+/// Render each session as its own Markdown table with a start/stop/duration
+/// header, followed by a summary of the total session count and active time.
+pub fn to_markdown(sessions: &[Session]) -> String {
+    let mut out = String::new();
+
+    for (i, session) in sessions.iter().enumerate() {
+        out.push_str(&format!(
+            "### Session {} — {} to {} ({})\n\n",
+            i + 1,
+            format_timestamp(session.start),
+            format_timestamp(session.stop),
+            format_duration(session.duration()),
+        ));
+
+        let mut buf = Vec::new();
+        MarkdownEncoder.encode(&session.events, &mut buf);
+        out.push_str(&String::from_utf8(buf).expect("markdown output is valid UTF-8"));
+        out.push('\n');
+    }
+
+    let total_active = sessions
+        .iter()
+        .fold(Duration::zero(), |acc, session| acc + session.duration());
+    out.push_str(&format!(
+        "**{} sessions, {} active**\n",
+        sessions.len(),
+        format_duration(total_active)
+    ));
+
+    out
+}
+
+fn format_duration(d: Duration) -> String {
+    let total_minutes = d.num_minutes();
+    format!("{}h{:02}m", total_minutes / 60, total_minutes % 60)
+}
+
+fn format_timestamp(ts: Option<DateTime<Utc>>) -> String {
+    ts.map(|ts| ts.format("%Y-%m-%dT%H:%M:%S").to_string())
+        .unwrap_or_else(|| "(no timestamps)".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventKind;
+    use chrono::TimeZone;
+
+    fn message(text: &str, ts: Option<DateTime<Utc>>) -> Event {
+        Event {
+            timestamp: ts,
+            kind: EventKind::Message {
+                name: "Alice".to_string(),
+                text: text.to_string(),
+            },
+        }
+    }
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap() + Duration::minutes(i64::from(minute))
+    }
+
+    #[test]
+    fn keeps_events_within_idle_gap_in_one_session() {
+        let events = vec![
+            message("hi", Some(at(0))),
+            message("still here", Some(at(10))),
+        ];
+        let sessions = split_into_sessions(events, Duration::minutes(30));
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].events.len(), 2);
+    }
+
+    #[test]
+    fn splits_on_gap_larger_than_idle_threshold() {
+        let events = vec![
+            message("hi", Some(at(0))),
+            message("back later", Some(at(60))),
+        ];
+        let sessions = split_into_sessions(events, Duration::minutes(30));
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].events.len(), 1);
+        assert_eq!(sessions[1].events.len(), 1);
+    }
+
+    #[test]
+    fn untimed_run_stays_a_single_timeless_session() {
+        let events = vec![message("hi", None), message("hey", None)];
+        let sessions = split_into_sessions(events, Duration::minutes(30));
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].start, None);
+        assert_eq!(sessions[0].stop, None);
+        assert_eq!(sessions[0].duration(), Duration::zero());
+    }
+
+    #[test]
+    fn session_bounds_use_min_max_not_first_last() {
+        // Out-of-order timestamps within one run (clock skew, or mixed
+        // sources) must not make `stop` precede `start`.
+        let session = Session::from_events(vec![
+            message("first", Some(at(10))),
+            message("second", Some(at(0))),
+        ]);
+
+        assert_eq!(session.start, Some(at(0)));
+        assert_eq!(session.stop, Some(at(10)));
+        assert_eq!(session.duration(), Duration::minutes(10));
+    }
+
+    #[test]
+    fn duration_is_clamped_to_zero_when_stop_precedes_start() {
+        let session = Session {
+            start: Some(at(10)),
+            stop: Some(at(0)),
+            events: Vec::new(),
+        };
+
+        assert_eq!(session.duration(), Duration::zero());
+    }
+}
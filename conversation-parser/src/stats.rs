@@ -0,0 +1,190 @@
+//! Per-speaker statistics over a parsed transcript, used by the `freq` mode.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::event::{Event, EventKind};
+use crate::formats::markdown::escape_md;
+
+/// Accumulated statistics for a single speaker.
+#[derive(Debug, Default)]
+pub struct SpeakerStats {
+    pub message_count: usize,
+    pub word_count: usize,
+    pub first_seen: Option<DateTime<Utc>>,
+    pub last_seen: Option<DateTime<Utc>>,
+    word_frequency: HashMap<String, usize>,
+}
+
+impl SpeakerStats {
+    fn record(&mut self, timestamp: Option<DateTime<Utc>>, text: &str) {
+        self.message_count += 1;
+
+        if let Some(ts) = timestamp {
+            self.first_seen.get_or_insert(ts);
+            self.last_seen = Some(ts);
+        }
+
+        for word in normalized_words(text) {
+            self.word_count += 1;
+            *self.word_frequency.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    /// The `n` most frequent words, most frequent first, ties broken
+    /// alphabetically for stable output.
+    pub fn top_words(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut words: Vec<(&str, usize)> = self
+            .word_frequency
+            .iter()
+            .map(|(w, &count)| (w.as_str(), count))
+            .collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        words.truncate(n);
+        words
+    }
+}
+
+/// Lowercase each word and strip leading/trailing punctuation.
+fn normalized_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+}
+
+/// Compute per-speaker statistics over a list of (merged) events.
+pub fn compute_stats(events: &[Event]) -> HashMap<String, SpeakerStats> {
+    let mut stats: HashMap<String, SpeakerStats> = HashMap::new();
+
+    for event in events {
+        if let EventKind::Message { name, text } = &event.kind {
+            stats.entry(name.clone()).or_default().record(event.timestamp, text);
+        }
+    }
+
+    stats
+}
+
+/// Render per-speaker statistics as a Markdown table, listing the top `top_n`
+/// words per speaker.
+pub fn to_markdown_table(stats: &HashMap<String, SpeakerStats>, top_n: usize) -> String {
+    let mut table = String::new();
+    table.push_str("| Speaker | Messages | Words | First | Last | Top Words |\n");
+    table.push_str("|---------|----------|-------|-------|------|-----------|\n");
+
+    let mut names: Vec<&String> = stats.keys().collect();
+    names.sort();
+
+    for name in names {
+        let s = &stats[name];
+        let first = format_timestamp(s.first_seen);
+        let last = format_timestamp(s.last_seen);
+        let top_words = s
+            .top_words(top_n)
+            .into_iter()
+            .map(|(word, count)| format!("{} ({})", word, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            escape_md(name),
+            s.message_count,
+            s.word_count,
+            first,
+            last,
+            escape_md(&top_words)
+        ));
+    }
+
+    table
+}
+
+fn format_timestamp(ts: Option<DateTime<Utc>>) -> String {
+    ts.map(|ts| ts.format("%Y-%m-%dT%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn message(name: &str, text: &str, ts: Option<DateTime<Utc>>) -> Event {
+        Event {
+            timestamp: ts,
+            kind: EventKind::Message {
+                name: name.to_string(),
+                text: text.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn counts_messages_and_words_per_speaker() {
+        let events = vec![
+            message("Alice", "hello world", None),
+            message("Bob", "hi", None),
+            message("Alice", "hello again", None),
+        ];
+        let stats = compute_stats(&events);
+
+        assert_eq!(stats["Alice"].message_count, 2);
+        assert_eq!(stats["Alice"].word_count, 4);
+        assert_eq!(stats["Bob"].message_count, 1);
+        assert_eq!(stats["Bob"].word_count, 1);
+    }
+
+    #[test]
+    fn tracks_first_and_last_seen() {
+        let t1 = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2024, 5, 1, 12, 5, 0).unwrap();
+        let events = vec![
+            message("Alice", "hi", Some(t1)),
+            message("Alice", "bye", Some(t2)),
+        ];
+        let stats = compute_stats(&events);
+
+        assert_eq!(stats["Alice"].first_seen, Some(t1));
+        assert_eq!(stats["Alice"].last_seen, Some(t2));
+    }
+
+    #[test]
+    fn top_words_are_case_folded_and_punctuation_stripped() {
+        let events = vec![message("Alice", "Hello, hello! World.", None)];
+        let stats = compute_stats(&events);
+
+        assert_eq!(
+            stats["Alice"].top_words(2),
+            vec![("hello", 2), ("world", 1)]
+        );
+    }
+
+    #[test]
+    fn top_words_breaks_ties_alphabetically() {
+        let events = vec![message("Alice", "banana apple cherry", None)];
+        let stats = compute_stats(&events);
+
+        assert_eq!(
+            stats["Alice"].top_words(3),
+            vec![("apple", 1), ("banana", 1), ("cherry", 1)]
+        );
+    }
+
+    #[test]
+    fn ignores_non_message_events() {
+        let events = vec![Event {
+            timestamp: None,
+            kind: EventKind::Join {
+                name: "Alice".to_string(),
+            },
+        }];
+        let stats = compute_stats(&events);
+
+        assert!(stats.is_empty());
+    }
+}